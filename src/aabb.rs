@@ -0,0 +1,49 @@
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> bool {
+        let origin = ray.origin();
+        let direction = ray.direction();
+        let mut t_min = t_min;
+        let mut t_max = t_max;
+        for a in 0..3 {
+            let inv_d = 1.0 / direction.axis(a);
+            let mut t0 = (self.min.axis(a) - origin.axis(a)) * inv_d;
+            let mut t1 = (self.max.axis(a) - origin.axis(a)) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t0.max(t_min);
+            t_max = t1.min(t_max);
+            if t_max <= t_min {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+pub fn surrounding_box(box0: Aabb, box1: Aabb) -> Aabb {
+    let small = Vec3::new(
+        box0.min.x().min(box1.min.x()),
+        box0.min.y().min(box1.min.y()),
+        box0.min.z().min(box1.min.z()),
+    );
+    let big = Vec3::new(
+        box0.max.x().max(box1.max.x()),
+        box0.max.y().max(box1.max.y()),
+        box0.max.z().max(box1.max.z()),
+    );
+    Aabb::new(small, big)
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::aabb::{surrounding_box, Aabb};
+use crate::object::{HitRecord, Object};
+use crate::ray::Ray;
+use crate::utils::random_int;
+
+/// An axis-aligned bounding-volume hierarchy over a set of objects, turning
+/// an O(n) linear scan into an O(log n) tree descent.
+pub struct BvhNode {
+    left: Arc<dyn Object>,
+    right: Arc<dyn Object>,
+    bbox: Aabb,
+}
+
+impl BvhNode {
+    pub fn new(mut objects: Vec<Arc<dyn Object>>) -> Self {
+        assert!(!objects.is_empty(), "BvhNode::new called with no objects");
+        let axis = random_int(0, 2) as usize;
+        objects.sort_by(|a, b| {
+            let box_a = a.bounding_box().expect("object has no bounding box");
+            let box_b = b.bounding_box().expect("object has no bounding box");
+            box_a
+                .min
+                .axis(axis)
+                .partial_cmp(&box_b.min.axis(axis))
+                .unwrap()
+        });
+
+        let (left, right): (Arc<dyn Object>, Arc<dyn Object>) = match objects.len() {
+            1 => (objects[0].clone(), objects[0].clone()),
+            2 => (objects[0].clone(), objects[1].clone()),
+            len => {
+                let mid = len / 2;
+                let right_half = objects.split_off(mid);
+                (
+                    Arc::new(BvhNode::new(objects)),
+                    Arc::new(BvhNode::new(right_half)),
+                )
+            }
+        };
+
+        let box_left = left.bounding_box().expect("object has no bounding box");
+        let box_right = right.bounding_box().expect("object has no bounding box");
+        let bbox = surrounding_box(box_left, box_right);
+
+        Self { left, right, bbox }
+    }
+}
+
+impl Object for BvhNode {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, t_min, t_max) {
+            return None;
+        }
+        let hit_left = self.left.hit(ray, t_min, t_max);
+        let closest_so_far = hit_left.as_ref().map_or(t_max, |rec| rec.t);
+        let hit_right = self.right.hit(ray, t_min, closest_so_far);
+        hit_right.or(hit_left)
+    }
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
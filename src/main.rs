@@ -1,33 +1,18 @@
+pub mod aabb;
+pub mod bvh;
 pub mod camera;
 pub mod object;
 pub mod ray;
+pub mod render;
 pub mod utils;
 pub mod vec3;
-use crate::camera::Camera;
-use crate::object::{Metal, Lambertian, Dielectric, Object, ObjectList, Sphere};
-use crate::ray::Ray;
-use crate::utils::random_float;
-use crate::vec3::{dot, unit_vector, write_color, Color, Vec3};
+use std::sync::Arc;
 
-fn ray_color(r: &Ray, world: &dyn Object, depth: i32) -> Color {
-    if depth <= 0 {
-        return Color::new(0.0, 0.0, 0.0);
-    }
-    let rec = world.hit(r, 0.001, std::f64::INFINITY);
-    match rec {
-        Some(rec) => {
-            match rec.material.scatter(r, &rec) {
-                Some((attenuation, scattered)) => attenuation * ray_color(&scattered, world, depth - 1),
-                None => Color::new(0.0, 0.0, 0.0),
-            }
-        }
-        None => {
-            let unit_direction = unit_vector(&r.direction());
-            let t = 0.5 * (unit_direction.y() + 1.0);
-            Color::new(1.0, 1.0, 1.0) * (1.0 - t) + Color::new(0.5, 0.7, 1.0) * t
-        }
-    }
-}
+use crate::bvh::BvhNode;
+use crate::camera::Camera;
+use crate::object::{Material, Metal, Lambertian, Dielectric, Object, ObjectList, Sphere};
+use crate::render::render;
+use crate::vec3::{write_color, Color, Vec3};
 
 fn main() {
     const ASPECT_RATIO: f64 = 16.0 / 9.0;
@@ -36,54 +21,79 @@ fn main() {
     const SAMPLES_PER_PIXEL: i32 = 50;
     const MAX_DEPTH: i32 = 30;
     let mut world = ObjectList::new();
-    let material_ground = Box::new(Lambertian {
+    let material_ground: Arc<dyn Material> = Arc::new(Lambertian {
         albedo: Color::new(0.8, 0.8, 0.0),
     });
-    let material_center = Box::new(Lambertian {
+    let material_center: Arc<dyn Material> = Arc::new(Lambertian {
         albedo: Color::new(0.1, 0.2, 0.5),
     });
-    let material_left = Box::new(Dielectric {
+    let material_left: Arc<dyn Material> = Arc::new(Dielectric {
         ref_idx: 1.5,
     });
-    let material_right = Box::new(Metal {
+    let material_right: Arc<dyn Material> = Arc::new(Metal {
         albedo: Color::new(0.8, 0.6, 0.2),
         fuzz: 0.0,
     });
-    world.push(Box::new(Sphere {
+    world.push(Arc::new(Sphere {
         center: Vec3::new(0.0, -100.5, -1.0),
         radius: 100.0,
         material: material_ground,
     }));
-    world.push(Box::new(Sphere {
+    world.push(Arc::new(Sphere {
         center: Vec3::new(0.0, 0.0, -1.0),
         radius: 0.5,
         material: material_center,
     }));
-    world.push(Box::new(Sphere {
+    world.push(Arc::new(Sphere {
         center: Vec3::new(-1.0, 0.0, -1.0),
         radius: 0.5,
         material: material_left.clone(),
     }));
-    world.push(Box::new(Sphere {
+    world.push(Arc::new(Sphere {
         center: Vec3::new(-1.0, 0.0, -1.0),
         radius: -0.4,
         material: material_left,
     }));
-    world.push(Box::new(Sphere {
+    world.push(Arc::new(Sphere {
         center: Vec3::new(1.0, 0.0, -1.0),
         radius: 0.5,
         material: material_right,
     }));
-    let cam = Camera::new();
+    let look_from = Vec3::new(3.0, 3.0, 2.0);
+    let look_at = Vec3::new(0.0, 0.0, -1.0);
+    let vup = Vec3::new(0.0, 1.0, 0.0);
+    let dist_to_focus = (look_from - look_at).len();
+    let aperture = 0.1;
+    let cam = Camera::new(
+        look_from,
+        look_at,
+        vup,
+        20.0,
+        ASPECT_RATIO,
+        aperture,
+        dist_to_focus,
+        0.0,
+        1.0,
+    );
+    let world = BvhNode::new(world.into_objects());
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let background = Color::new(0.5, 0.7, 1.0);
+    let buffer = render(
+        Arc::new(world) as Arc<dyn Object>,
+        Arc::new(cam),
+        background,
+        IMAGE_WIDTH,
+        IMAGE_HEIGHT,
+        SAMPLES_PER_PIXEL,
+        MAX_DEPTH,
+        threads,
+    );
     println!("P3\n{IMAGE_WIDTH} {IMAGE_HEIGHT}\n255");
     for j in (0..IMAGE_HEIGHT).rev() {
-        eprintln!("Scalines remaining: {j}");
         for i in 0..IMAGE_WIDTH {
-            let mut color = Color::new(0.0, 0.0, 0.0);
-            for _ in 0..SAMPLES_PER_PIXEL {
-                let u = (i as f64 + random_float()) / (IMAGE_WIDTH - 1) as f64;
-                color = color + ray_color(&r, &world, MAX_DEPTH);
-            }
+            let color = buffer[(j * IMAGE_WIDTH + i) as usize];
             println!("{}", write_color(color, SAMPLES_PER_PIXEL));
         }
     }
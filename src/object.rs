@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use crate::aabb::{surrounding_box, Aabb};
 use crate::ray::Ray;
 use crate::utils::random_float;
 use crate::vec3::{dot, Vec3};
@@ -6,7 +9,7 @@ pub struct HitRecord {
     pub p: Vec3,
     pub normal: Vec3,
     pub front_face: bool,
-    pub material: Box<dyn Material>,
+    pub material: Arc<dyn Material>,
 }
 
 impl HitRecord {
@@ -20,8 +23,9 @@ impl HitRecord {
     }
 }
 
-pub trait Object {
+pub trait Object: Send + Sync {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord>;
+    fn bounding_box(&self) -> Option<Aabb>;
 }
 
 fn reflect(v: Vec3, n: Vec3) -> Vec3 {
@@ -35,9 +39,11 @@ fn refract(uv: Vec3, n: Vec3, etai_over_etat: f64) -> Vec3 {
     r_out_perp + r_out_parallel
 }
 
-pub trait Material {
+pub trait Material: Send + Sync {
     fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Vec3, Ray)>;
-    fn clone(&self) -> Box<dyn Material>;
+    fn emitted(&self) -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
 }
 
 #[derive(Clone)]
@@ -70,12 +76,9 @@ impl Material for Dielectric {
         } else {
             refract(unit_direction, rec.normal, refraction_ratio)
         };
-        let scattered = Ray::new(rec.p, direction);
+        let scattered = Ray::new(rec.p, direction, ray.time());
         Some((attenuation, scattered))
     }
-    fn clone(&self) -> Box<dyn Material> {
-        Box::new(Dielectric { ref_idx: self.ref_idx })
-    }
 }
 
 pub struct Metal {
@@ -86,16 +89,17 @@ pub struct Metal {
 impl Material for Metal {
     fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Vec3, Ray)> {
         let reflected = reflect(ray.direction().unit_vector(), rec.normal);
-        let scattered = Ray::new(rec.p, reflected + Vec3::random_in_unit_sphere() * self.fuzz);
+        let scattered = Ray::new(
+            rec.p,
+            reflected + Vec3::random_in_unit_sphere() * self.fuzz,
+            ray.time(),
+        );
         if dot(scattered.direction(), rec.normal) > 0.0 {
             Some((self.albedo, scattered))
         } else {
             None
         }
     }
-    fn clone(&self) -> Box<dyn Material> {
-        Box::new(Metal { albedo: self.albedo, fuzz: self.fuzz})
-    }
 }
 
 pub struct Lambertian {
@@ -114,18 +118,34 @@ impl Material for Lambertian {
         if scatter_direction.near_zero() {
             return None;
         }
-        let scattered = Ray::new(rec.p, scatter_direction);
+        let scattered = Ray::new(rec.p, scatter_direction, ray.time());
         Some((self.albedo, scattered))
     }
-    fn clone(&self) -> Box<dyn Material> {
-        Box::new(Lambertian::new(self.albedo))
+}
+
+pub struct DiffuseLight {
+    pub emit: Vec3,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Vec3) -> Self {
+        Self { emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _ray: &Ray, _rec: &HitRecord) -> Option<(Vec3, Ray)> {
+        None
+    }
+    fn emitted(&self) -> Vec3 {
+        self.emit
     }
 }
 
 pub struct Sphere {
     pub center: Vec3,
     pub radius: f64,
-    pub material: Box<dyn Material>,
+    pub material: Arc<dyn Material>,
 }
 
 impl Object for Sphere {
@@ -139,9 +159,9 @@ impl Object for Sphere {
             return None;
         }
         let sqrtd = discriminant.sqrt();
-        let root = (-half_b - sqrtd) / a;
+        let mut root = (-half_b - sqrtd) / a;
         if root < t_min || t_max < root {
-            let root = (-half_b + sqrtd) / a;
+            root = (-half_b + sqrtd) / a;
             if root < t_min || t_max < root {
                 return None;
             }
@@ -152,16 +172,75 @@ impl Object for Sphere {
             p: ray.at(t),
             normal: Vec3::new(0.0, 0.0, 0.0),
             front_face: false,
-            material: self.material.clone(),
+            material: Arc::clone(&self.material),
         };
         let outward_normal = (rec.p - self.center) / self.radius;
         rec.set_face_normal(ray, outward_normal);
         Some(rec)
     }
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        Some(Aabb::new(self.center - radius, self.center + radius))
+    }
+}
+
+pub struct MovingSphere {
+    pub center0: Vec3,
+    pub center1: Vec3,
+    pub time0: f64,
+    pub time1: f64,
+    pub radius: f64,
+    pub material: Arc<dyn Material>,
+}
+
+impl MovingSphere {
+    pub fn center(&self, time: f64) -> Vec3 {
+        self.center0
+            + (self.center1 - self.center0) * ((time - self.time0) / (self.time1 - self.time0))
+    }
+}
+
+impl Object for MovingSphere {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let center = self.center(ray.time());
+        let oc = ray.origin() - center;
+        let a = ray.direction().len_squared();
+        let half_b = dot(oc, ray.direction());
+        let c = oc.len_squared() - self.radius * self.radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrtd = discriminant.sqrt();
+        let mut root = (-half_b - sqrtd) / a;
+        if root < t_min || t_max < root {
+            root = (-half_b + sqrtd) / a;
+            if root < t_min || t_max < root {
+                return None;
+            }
+        }
+        let t = root;
+        let mut rec = HitRecord {
+            t,
+            p: ray.at(t),
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        let outward_normal = (rec.p - center) / self.radius;
+        rec.set_face_normal(ray, outward_normal);
+        Some(rec)
+    }
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        let box0 = Aabb::new(self.center(self.time0) - radius, self.center(self.time0) + radius);
+        let box1 = Aabb::new(self.center(self.time1) - radius, self.center(self.time1) + radius);
+        Some(surrounding_box(box0, box1))
+    }
 }
 
 pub struct ObjectList {
-    objects: Vec<Box<dyn Object>>,
+    objects: Vec<Arc<dyn Object>>,
 }
 
 impl ObjectList {
@@ -170,15 +249,18 @@ impl ObjectList {
             objects: Vec::new(),
         }
     }
-    pub fn push(&mut self, object: Box<dyn Object>) {
+    pub fn push(&mut self, object: Arc<dyn Object>) {
         self.objects.push(object);
     }
-    pub fn get(&self, index: usize) -> Option<&Box<dyn Object>> {
+    pub fn get(&self, index: usize) -> Option<&Arc<dyn Object>> {
         self.objects.get(index)
     }
     pub fn clear(&mut self) {
         self.objects.clear();
     }
+    pub fn into_objects(self) -> Vec<Arc<dyn Object>> {
+        self.objects
+    }
 }
 
 impl Object for ObjectList {
@@ -193,4 +275,15 @@ impl Object for ObjectList {
         }
         hit_anything
     }
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut output_box: Option<Aabb> = None;
+        for object in &self.objects {
+            let object_box = object.bounding_box()?;
+            output_box = Some(match output_box {
+                Some(running) => surrounding_box(running, object_box),
+                None => object_box,
+            });
+        }
+        output_box
+    }
 }
@@ -3,11 +3,12 @@ use crate::vec3::{ Vec3, Point };
 pub struct Ray {
     origin: Point,
     direction: Vec3,
+    time: f64,
 }
 
 impl Ray {
-    pub fn new(origin: Point, direction: Vec3) -> Self {
-        Self { origin, direction }
+    pub fn new(origin: Point, direction: Vec3, time: f64) -> Self {
+        Self { origin, direction, time }
     }
     pub fn origin(&self) -> Point {
         self.origin.clone()
@@ -15,6 +16,9 @@ impl Ray {
     pub fn direction(&self) -> Vec3 {
         self.direction.clone()
     }
+    pub fn time(&self) -> f64 {
+        self.time
+    }
     pub fn at(&self, t: f64) -> Point {
         self.origin + self.direction * t
     }
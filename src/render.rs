@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::thread;
+
+use crate::camera::Camera;
+use crate::object::Object;
+use crate::ray::Ray;
+use crate::utils::random_float;
+use crate::vec3::Color;
+
+fn ray_color(r: &Ray, background: Color, world: &dyn Object, depth: i32) -> Color {
+    if depth <= 0 {
+        return Color::new(0.0, 0.0, 0.0);
+    }
+    let rec = match world.hit(r, 0.001, std::f64::INFINITY) {
+        Some(rec) => rec,
+        None => return background,
+    };
+    let emitted = rec.material.emitted();
+    match rec.material.scatter(r, &rec) {
+        Some((attenuation, scattered)) => {
+            emitted + attenuation * ray_color(&scattered, background, world, depth - 1)
+        }
+        None => emitted,
+    }
+}
+
+/// Renders the scene into a pixel buffer indexed by `y * width + x`, with
+/// `y = 0` at the bottom of the image, splitting the rows across `threads`
+/// worker threads.
+pub fn render(
+    world: Arc<dyn Object>,
+    camera: Arc<Camera>,
+    background: Color,
+    width: i32,
+    height: i32,
+    samples_per_pixel: i32,
+    max_depth: i32,
+    threads: usize,
+) -> Vec<Color> {
+    let threads = threads.max(1);
+    let rows_per_band = (height as usize).div_ceil(threads);
+    let mut bands = Vec::new();
+    let mut start = 0;
+    while start < height as usize {
+        let end = (start + rows_per_band).min(height as usize);
+        bands.push((start, end));
+        start = end;
+    }
+
+    let mut buffer = vec![Color::new(0.0, 0.0, 0.0); (width as usize) * (height as usize)];
+    thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for (start, end) in bands {
+            let world = Arc::clone(&world);
+            let camera = Arc::clone(&camera);
+            handles.push(scope.spawn(move || {
+                let mut rows = Vec::with_capacity((end - start) * width as usize);
+                for j in start..end {
+                    for i in 0..width {
+                        let mut color = Color::new(0.0, 0.0, 0.0);
+                        for _ in 0..samples_per_pixel {
+                            let u = (i as f64 + random_float()) / (width - 1) as f64;
+                            let v = (j as f64 + random_float()) / (height - 1) as f64;
+                            let r = camera.get_ray(u, v);
+                            color = color + ray_color(&r, background, world.as_ref(), max_depth);
+                        }
+                        rows.push(color);
+                    }
+                }
+                (start, rows)
+            }));
+        }
+        for handle in handles {
+            let (start, rows) = handle.join().unwrap();
+            for (offset, color) in rows.into_iter().enumerate() {
+                buffer[start * width as usize + offset] = color;
+            }
+        }
+    });
+    buffer
+}
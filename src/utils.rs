@@ -12,6 +12,10 @@ pub fn random_floatmx(min: f64, max: f64) -> f64 {
     min + (max - min) * random::<f64>()
 }
 
+pub fn random_int(min: i32, max: i32) -> i32 {
+    random_floatmx(min as f64, (max + 1) as f64) as i32
+}
+
 pub fn clamp(x: f64, min: f64, max: f64) -> f64 {
     if x < min {
         min
@@ -20,6 +20,9 @@ impl Vec3 {
     pub fn z(&self) -> f64 {
         self.e[2]
     }
+    pub fn axis(&self, n: usize) -> f64 {
+        self.e[n]
+    }
     pub fn len_squared(&self) -> f64 {
         self.e[0] * self.e[0] + self.e[1] * self.e[1] + self.e[2] * self.e[2]
     }
@@ -63,6 +66,15 @@ impl Vec3 {
             -in_unit_sphere
         }
     }
+    pub fn random_in_unit_disk() -> Vec3 {
+        loop {
+            let p = Vec3::new(random_floatmx(-1.0, 1.0), random_floatmx(-1.0, 1.0), 0.0);
+            if p.len_squared() >= 1.0 {
+                continue;
+            }
+            return p;
+        }
+    }
     pub fn near_zero(&self) -> bool {
         let s = 1e-8;
         (self.e[0].abs() < s) && (self.e[1].abs() < s) && (self.e[2].abs() < s)